@@ -29,7 +29,7 @@ use winapi::{
     um::handleapi::INVALID_HANDLE_VALUE,
     um::processenv::GetStdHandle,
     um::winbase::STD_INPUT_HANDLE,
-    um::wincon::{ENABLE_ECHO_INPUT, ENABLE_VIRTUAL_TERMINAL_INPUT},
+    um::wincon::{ENABLE_ECHO_INPUT, ENABLE_MOUSE_INPUT, ENABLE_VIRTUAL_TERMINAL_INPUT},
 };
 
 #[cfg(not(windows))]
@@ -93,10 +93,161 @@ pub enum Key {
     ///
     /// Note that certain keys may not be modifiable with `ctrl`, due to limitations of terminals.
     Ctrl(char),
+    /// A key carrying a Shift/Alt/Ctrl modifier.
+    ///
+    /// Produced for `xterm`-style CSI sequences such as `ESC [ 1 ; 5 C`
+    /// (Ctrl+Right) or `ESC [ 3 ; 2 ~` (Shift+Delete). The `key` field holds
+    /// the unmodified base key.
+    Modified {
+        key: Box<Key>,
+        shift: bool,
+        ctrl: bool,
+        alt: bool,
+    },
+    /// Up arrow in keypad/application-cursor mode (`ESC O A`).
+    KeypadUp,
+    /// Down arrow in keypad/application-cursor mode (`ESC O B`).
+    KeypadDown,
+    /// Right arrow in keypad/application-cursor mode (`ESC O C`).
+    KeypadRight,
+    /// Left arrow in keypad/application-cursor mode (`ESC O D`).
+    KeypadLeft,
+    /// Home key in keypad/application-cursor mode (`ESC O H`).
+    KeypadHome,
+    /// End key in keypad/application-cursor mode (`ESC O F`).
+    KeypadEnd,
+    /// A mouse event decoded from an SGR mouse report.
+    ///
+    /// Reported when mouse capture is enabled. `x`/`y` are 1-based
+    /// column/row. `button` holds the button code (low 2 bits: 0=left,
+    /// 1=middle, 2=right; bit 5 = motion, bits 6-7 = wheel) with the modifier
+    /// bits masked off.
+    Mouse {
+        button: u8,
+        x: u16,
+        y: u16,
+        pressed: bool,
+        shift: bool,
+        ctrl: bool,
+        alt: bool,
+    },
+    /// A block of bracketed-paste text.
+    ///
+    /// Produced between the `ESC [ 2 0 0 ~` and `ESC [ 2 0 1 ~` markers when
+    /// bracketed paste is enabled. The contents may contain newlines and
+    /// control characters that must not be interpreted as commands.
+    Paste(String),
     /// Other key.
     Other(Vec<u8>),
 }
 
+impl Key {
+    /// Render this key back into the terminal byte sequence that would have
+    /// produced it.
+    ///
+    /// This is the inverse of parsing: feeding the output back through
+    /// [`Getch::getch`] yields the original key for all unambiguous variants.
+    /// It is useful when proxying decoded input to a child process, e.g. in a
+    /// terminal multiplexer or PTY forwarder.
+    pub fn into_bytes(self) -> Vec<u8> {
+        match self {
+            Key::EOF       => vec![b'\0'],
+            Key::Backspace => vec![b'\x08'],
+            Key::Delete    => vec![b'\x7F'],
+            Key::Esc       => vec![b'\x1B'],
+            Key::Up        => vec![b'\x1B', b'[', b'A'],
+            Key::Down      => vec![b'\x1B', b'[', b'B'],
+            Key::Right     => vec![b'\x1B', b'[', b'C'],
+            Key::Left      => vec![b'\x1B', b'[', b'D'],
+            Key::End       => vec![b'\x1B', b'[', b'F'],
+            Key::Home      => vec![b'\x1B', b'[', b'H'],
+            Key::BackTab   => vec![b'\x1B', b'[', b'Z'],
+            Key::KeypadUp    => vec![b'\x1B', b'O', b'A'],
+            Key::KeypadDown  => vec![b'\x1B', b'O', b'B'],
+            Key::KeypadRight => vec![b'\x1B', b'O', b'C'],
+            Key::KeypadLeft  => vec![b'\x1B', b'O', b'D'],
+            Key::KeypadHome  => vec![b'\x1B', b'O', b'H'],
+            Key::KeypadEnd   => vec![b'\x1B', b'O', b'F'],
+            Key::Insert    => vec![b'\x1B', b'[', b'2', b'~'],
+            Key::PageUp    => vec![b'\x1B', b'[', b'5', b'~'],
+            Key::PageDown  => vec![b'\x1B', b'[', b'6', b'~'],
+            Key::F(n) => match n {
+                1..=4 => vec![b'\x1B', b'O', b'P' + n - 1],
+                5     => vec![b'\x1B', b'[', b'1', b'5', b'~'],
+                6..=10 => {
+                    let v = n + 11;
+                    vec![b'\x1B', b'[', b'0' + v / 10, b'0' + v % 10, b'~']
+                }
+                _ => {
+                    let v = n + 12;
+                    vec![b'\x1B', b'[', b'0' + v / 10, b'0' + v % 10, b'~']
+                }
+            },
+            Key::Char(c) => c.to_string().into_bytes(),
+            Key::Alt(c) => {
+                let mut bytes = vec![b'\x1B'];
+                bytes.extend_from_slice(c.to_string().as_bytes());
+                bytes
+            }
+            Key::Ctrl(c) => match c {
+                'a'..='z' => vec![c as u8 - b'a' + 0x01],
+                '4'..='7' => vec![c as u8 - b'4' + 0x1C],
+                _ => c.to_string().into_bytes(),
+            },
+            Key::Modified { key, shift, ctrl, alt } => {
+                modified_into_bytes(*key, shift, ctrl, alt)
+            }
+            Key::Mouse { button, x, y, pressed, shift, ctrl, alt } => {
+                let b = button as u32
+                    | if shift { 0b0000_0100 } else { 0 }
+                    | if alt { 0b0000_1000 } else { 0 }
+                    | if ctrl { 0b0001_0000 } else { 0 };
+                let final_byte = if pressed { 'M' } else { 'm' };
+                format!("\x1b[<{b};{x};{y}{final_byte}").into_bytes()
+            }
+            Key::Paste(s) => {
+                let mut bytes = b"\x1b[200~".to_vec();
+                bytes.extend_from_slice(s.as_bytes());
+                bytes.extend_from_slice(b"\x1b[201~");
+                bytes
+            }
+            Key::Other(v) => v,
+        }
+    }
+}
+
+/// Re-encode a modified key as its `xterm` CSI sequence (`ESC [ 1 ; m X` for
+/// cursor and F1-F4 keys, `ESC [ n ; m ~` for editing/function keys).
+///
+/// Bases that have no CSI modifier encoding are emitted unmodified.
+fn modified_into_bytes(key: Key, shift: bool, ctrl: bool, alt: bool) -> Vec<u8> {
+    let m = 1 + (shift as u8) + ((alt as u8) << 1) + ((ctrl as u8) << 2);
+
+    // The first parameter and final byte of the base key's CSI encoding. Keys
+    // with a letter final byte use `1` as the placeholder first parameter.
+    let (first, final_byte): (u8, u8) = match key {
+        Key::Up    => (1, b'A'),
+        Key::Down  => (1, b'B'),
+        Key::Right => (1, b'C'),
+        Key::Left  => (1, b'D'),
+        Key::End   => (1, b'F'),
+        Key::Home  => (1, b'H'),
+        Key::F(n @ 1..=4) => (1, b'P' + n - 1),
+        Key::Insert   => (2, b'~'),
+        Key::Delete   => (3, b'~'),
+        Key::PageUp   => (5, b'~'),
+        Key::PageDown => (6, b'~'),
+        Key::F(5)          => (15, b'~'),
+        Key::F(n @ 6..=10) => (n + 11, b'~'),
+        Key::F(n @ 11..=12) => (n + 12, b'~'),
+        // Bases without a CSI modifier form (e.g. `Char`, `Backspace`) can't
+        // carry a modifier; emit them unmodified.
+        other => return other.into_bytes(),
+    };
+
+    format!("\x1b[{first};{m}{}", final_byte as char).into_bytes()
+}
+
 impl Getch {
     #[cfg(windows)]
     #[allow(clippy::new_without_default)]
@@ -115,6 +266,17 @@ impl Getch {
             leftover: RefCell::new(None),
         }
     }
+
+    /// Like [`new`](Self::new), but puts the terminal into full raw mode.
+    ///
+    /// On Windows the console mode installed by [`new`](Self::new) is already
+    /// raw — it keeps only `ENABLE_VIRTUAL_TERMINAL_INPUT`, which clears line
+    /// input, echo and processed input — so this is equivalent to `new`.
+    #[cfg(windows)]
+    #[allow(clippy::new_without_default)]
+    pub fn new_raw() -> Self {
+        Self::new()
+    }
     #[cfg(not(windows))]
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
@@ -139,8 +301,111 @@ impl Getch {
         }
     }
 
+    /// Like [`new`](Self::new), but puts the terminal into full raw mode via
+    /// `cfmakeraw`.
+    ///
+    /// In addition to the flags cleared by [`new`](Self::new), this also
+    /// disables input mapping such as CR→NL translation (`ICRNL`) and flow
+    /// control (`IXON`), so every byte reaches the application untouched.
+    #[cfg(not(windows))]
+    #[allow(clippy::new_without_default)]
+    pub fn new_raw() -> Self {
+        let stdin = std::io::stdin();
+
+        // Quering original as a separate, since `Termios` does not implement copy
+        let orig_term       = termios::tcgetattr(&stdin).unwrap();
+        let mut raw_termios = termios::tcgetattr(&stdin).unwrap();
+
+        termios::cfmakeraw(&mut raw_termios);
+
+        termios::tcsetattr(&stdin, termios::SetArg::TCSADRAIN, &raw_termios).unwrap();
+
+        Self {
+            orig_term,
+            leftover: RefCell::new(None),
+        }
+    }
+
     #[allow(clippy::unused_io_amount)]
     pub fn getch(&self) -> Result<Key, std::io::Error> {
+        self.read_key()
+    }
+
+    /// Read a key without blocking.
+    ///
+    /// Returns `Ok(None)` immediately when no input is ready, otherwise reads a
+    /// key as [`getch`](Self::getch) would.
+    #[cfg(not(windows))]
+    pub fn try_getch(&self) -> Result<Option<Key>, std::io::Error> {
+        self.getch_timeout(std::time::Duration::ZERO)
+    }
+
+    /// Read a key, waiting at most `dur` for input to become available.
+    ///
+    /// Returns `Ok(None)` if the timeout elapses before any input is ready.
+    /// Once the first byte is ready it is consumed and any follow-on
+    /// escape-sequence bytes are read normally.
+    #[cfg(not(windows))]
+    pub fn getch_timeout(&self, dur: std::time::Duration) -> Result<Option<Key>, std::io::Error> {
+        use nix::poll::{poll, PollFd, PollFlags, PollTimeout};
+        use std::os::fd::AsFd;
+
+        // A leftover byte from a previous read is already buffered.
+        if self.leftover.borrow().is_some() {
+            return self.read_key().map(Some);
+        }
+
+        let stdin = std::io::stdin();
+        let timeout = PollTimeout::try_from(dur).unwrap_or(PollTimeout::MAX);
+        let mut fds = [PollFd::new(stdin.as_fd(), PollFlags::POLLIN)];
+
+        let ready = poll(&mut fds, timeout)
+            .map_err(|e| std::io::Error::from_raw_os_error(e as i32))?;
+
+        if ready == 0 {
+            Ok(None)
+        } else {
+            self.read_key().map(Some)
+        }
+    }
+
+    /// Read a key without blocking.
+    ///
+    /// Returns `Ok(None)` immediately when no input is ready, otherwise reads a
+    /// key as [`getch`](Self::getch) would.
+    #[cfg(windows)]
+    pub fn try_getch(&self) -> Result<Option<Key>, std::io::Error> {
+        self.getch_timeout(std::time::Duration::ZERO)
+    }
+
+    /// Read a key, waiting at most `dur` for input to become available.
+    ///
+    /// Returns `Ok(None)` if the timeout elapses before any input is ready.
+    #[cfg(windows)]
+    pub fn getch_timeout(&self, dur: std::time::Duration) -> Result<Option<Key>, std::io::Error> {
+        use winapi::um::synchapi::WaitForSingleObject;
+        use winapi::um::winbase::{INFINITE, WAIT_OBJECT_0};
+
+        if self.leftover.borrow().is_some() {
+            return self.read_key().map(Some);
+        }
+
+        unsafe {
+            let input_handle = GetStdHandle(STD_INPUT_HANDLE);
+            let millis = match u32::try_from(dur.as_millis()) {
+                Ok(ms) => ms,
+                Err(_) => INFINITE,
+            };
+            if WaitForSingleObject(input_handle, millis) == WAIT_OBJECT_0 {
+                self.read_key().map(Some)
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    #[allow(clippy::unused_io_amount)]
+    fn read_key(&self) -> Result<Key, std::io::Error> {
         let source = &mut std::io::stdin();
         let mut buf: [u8; 2] = [0; 2];
 
@@ -223,6 +488,87 @@ pub fn disable_echo_input() {
     }
 }
 
+/// Enable bracketed paste mode.
+///
+/// While enabled, a terminal wraps pasted text in `ESC [ 2 0 0 ~` / `ESC [ 2
+/// 0 1 ~` markers, which [`Getch::getch`] reports as a single [`Key::Paste`].
+///
+/// This is a no-op on Windows, whose console has no bracketed-paste mode to
+/// toggle (the VT input flag is owned by [`Getch`]).
+pub fn enable_bracketed_paste() {
+    #[cfg(not(windows))]
+    {
+        use std::io::Write;
+        let _ = std::io::stdout().write_all(b"\x1b[?2004h");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Disable bracketed paste mode.
+///
+/// This is a no-op on Windows; see [`enable_bracketed_paste`].
+pub fn disable_bracketed_paste() {
+    #[cfg(not(windows))]
+    {
+        use std::io::Write;
+        let _ = std::io::stdout().write_all(b"\x1b[?2004l");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Enable SGR mouse capture.
+///
+/// While enabled, mouse button presses, releases and wheel events are
+/// reported by the terminal and decoded into [`Key::Mouse`]. On Unix this
+/// turns on normal tracking (`?1000h`) with SGR encoding (`?1006h`), which
+/// does not report mouse motion.
+pub fn enable_mouse_capture() {
+    #[cfg(windows)]
+    unsafe {
+        let input_handle = GetStdHandle(STD_INPUT_HANDLE);
+        let mut console_mode: DWORD = 0;
+
+        if input_handle == INVALID_HANDLE_VALUE {
+            return;
+        }
+
+        if GetConsoleMode(input_handle, &mut console_mode) != 0 {
+            SetConsoleMode(input_handle, console_mode | ENABLE_MOUSE_INPUT);
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        use std::io::Write;
+        let _ = std::io::stdout().write_all(b"\x1b[?1000h\x1b[?1006h");
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Disable SGR mouse capture.
+pub fn disable_mouse_capture() {
+    #[cfg(windows)]
+    unsafe {
+        let input_handle = GetStdHandle(STD_INPUT_HANDLE);
+        let mut console_mode: DWORD = 0;
+
+        if input_handle == INVALID_HANDLE_VALUE {
+            return;
+        }
+
+        if GetConsoleMode(input_handle, &mut console_mode) != 0 {
+            SetConsoleMode(input_handle, console_mode & !ENABLE_MOUSE_INPUT);
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        use std::io::Write;
+        let _ = std::io::stdout().write_all(b"\x1b[?1006l\x1b[?1000l");
+        let _ = std::io::stdout().flush();
+    }
+}
+
 /// Parse an Event from `item` and possibly subsequent bytes through `iter`.
 fn parse_key<I>(item: u8, iter: &mut I) -> Result<Key, std::io::Error>
 where
@@ -236,6 +582,13 @@ where
                     match iter.next() {
                         // F1-F4
                         Some(Ok(val @ b'P'..=b'S')) => Key::F(1 + val - b'P'),
+                        // Application-cursor / keypad navigation keys.
+                        Some(Ok(b'A')) => Key::KeypadUp,
+                        Some(Ok(b'B')) => Key::KeypadDown,
+                        Some(Ok(b'C')) => Key::KeypadRight,
+                        Some(Ok(b'D')) => Key::KeypadLeft,
+                        Some(Ok(b'H')) => Key::KeypadHome,
+                        Some(Ok(b'F')) => Key::KeypadEnd,
                         Some(Ok(val)) => Key::Other(vec![b'\x1B', b'O', val]),
                         _ => Key::Other(vec![b'\x1B', b'O']),
                     }
@@ -275,6 +628,7 @@ where
             Some(Ok(val)) => Key::Other(vec![b'\x1B', b'[', b'[', val]),
             _ => Key::Other(vec![b'\x1B', b'[', b'[']),
         },
+        Some(Ok(b'<')) => parse_sgr_mouse(iter),
         Some(Ok(b'A')) => Key::Up,
         Some(Ok(b'B')) => Key::Down,
         Some(Ok(b'C')) => Key::Right,
@@ -292,45 +646,68 @@ where
                 buf.push(c);
                 c = iter.next().unwrap().unwrap();
             }
-            match c {
-                // Special key code.
-                b'~' => {
-                    let str_buf = std::str::from_utf8(&buf).unwrap();
+            let final_byte = c;
 
-                    // This CSI sequence can be a list of semicolon-separated
-                    // numbers.
-                    let nums: Vec<u8> = str_buf.split(';').map(|n| n.parse().unwrap()).collect();
+            // This CSI sequence can be a list of semicolon-separated numbers.
+            let parsed: Option<Vec<u8>> = std::str::from_utf8(&buf)
+                .ok()
+                .and_then(|s| s.split(';').map(|n| n.parse().ok()).collect());
 
-                    if nums.is_empty() || nums.len() > 1 {
-                        let mut keys = vec![b'\x1B', b'['];
-                        keys.append(&mut buf);
-                        return Ok(Key::Other(keys));
-                    }
+            let fallback = || {
+                let mut keys = vec![b'\x1B', b'['];
+                keys.extend_from_slice(&buf);
+                keys.push(final_byte);
+                Key::Other(keys)
+            };
 
-                    match nums[0] {
-                        1 | 7 => Key::Home,
-                        2     => Key::Insert,
-                        3     => Key::Delete,
-                        4 | 8 => Key::End,
-                        5     => Key::PageUp,
-                        6     => Key::PageDown,
-                        v @ 11..=15 => Key::F(v - 10),
-                        v @ 17..=21 => Key::F(v - 11),
-                        v @ 23..=24 => Key::F(v - 12),
-                        _ => {
-                            let mut keys = vec![b'\x1B', b'['];
-                            keys.append(&mut buf);
-                            keys.push(nums[0]);
-                            return Ok(Key::Other(keys));
-                        }
+            let nums = match parsed {
+                Some(nums) if !nums.is_empty() => nums,
+                _ => return Ok(fallback()),
+            };
+
+            // Decode the base key from the first parameter and the final byte,
+            // exactly as an unmodified sequence would be decoded.
+            let base = match final_byte {
+                // Special key code.
+                b'~' => match nums[0] {
+                    // Start of a bracketed paste: buffer everything up to the
+                    // `ESC [ 2 0 1 ~` end marker and return it as a single key.
+                    200 => return Ok(parse_bracketed_paste(iter)),
+                    1 | 7 => Key::Home,
+                    2     => Key::Insert,
+                    3     => Key::Delete,
+                    4 | 8 => Key::End,
+                    5     => Key::PageUp,
+                    6     => Key::PageDown,
+                    v @ 11..=15 => Key::F(v - 10),
+                    v @ 17..=21 => Key::F(v - 11),
+                    v @ 23..=24 => Key::F(v - 12),
+                    _ => return Ok(fallback()),
+                },
+                b'A' => Key::Up,
+                b'B' => Key::Down,
+                b'C' => Key::Right,
+                b'D' => Key::Left,
+                b'F' => Key::End,
+                b'H' => Key::Home,
+                val @ b'P'..=b'S' => Key::F(1 + val - b'P'),
+                _ => return Ok(fallback()),
+            };
+
+            // A second parameter carries the active modifiers, encoded as
+            // `1 + bitmask` where bit 0 = Shift, bit 1 = Alt, bit 2 = Ctrl.
+            match nums.get(1) {
+                Some(&m) if m >= 1 => {
+                    let bits = m - 1;
+                    Key::Modified {
+                        key:   Box::new(base),
+                        shift: bits & 0b001 != 0,
+                        alt:   bits & 0b010 != 0,
+                        ctrl:  bits & 0b100 != 0,
                     }
                 }
-                _ => {
-                    let mut keys = vec![b'\x1B', b'['];
-                    keys.append(&mut buf);
-                    keys.push(c);
-                    return Ok(Key::Other(keys));
-                }
+                Some(_) => return Ok(fallback()),
+                None => base,
             }
         }
         Some(Ok(c)) => Key::Other(vec![b'\x1B', b'[', c]),
@@ -338,6 +715,107 @@ where
     })
 }
 
+/// Parses an SGR mouse report of the form `ESC [ < b ; x ; y (M|m)`, just
+/// after reading the leading `<`.
+///
+/// Returns [`Key::Other`] for any malformed report.
+fn parse_sgr_mouse<I>(iter: &mut I) -> Key
+where
+    I: Iterator<Item = Result<u8, std::io::Error>>,
+{
+    let mut buf = Vec::new();
+    let final_byte = loop {
+        match iter.next() {
+            Some(Ok(c @ (b'M' | b'm'))) => break c,
+            Some(Ok(c @ (b'0'..=b'9' | b';'))) => buf.push(c),
+            _ => {
+                let mut keys = vec![b'\x1B', b'[', b'<'];
+                keys.extend_from_slice(&buf);
+                return Key::Other(keys);
+            }
+        }
+    };
+
+    let fallback = || {
+        let mut keys = vec![b'\x1B', b'[', b'<'];
+        keys.extend_from_slice(&buf);
+        keys.push(final_byte);
+        Key::Other(keys)
+    };
+
+    let nums: Option<Vec<u32>> = std::str::from_utf8(&buf)
+        .ok()
+        .and_then(|s| s.split(';').map(|n| n.parse().ok()).collect());
+
+    let nums = match nums {
+        Some(n) if n.len() == 3 => n,
+        _ => return fallback(),
+    };
+
+    let b = nums[0];
+    let (x, y) = match (u16::try_from(nums[1]), u16::try_from(nums[2])) {
+        (Ok(x), Ok(y)) => (x, y),
+        _ => return fallback(),
+    };
+
+    Key::Mouse {
+        // Strip the modifier bits (Shift=4, Alt=8, Ctrl=16) from the button.
+        button: (b & !0b0001_1100) as u8,
+        x,
+        y,
+        pressed: final_byte == b'M',
+        shift: b & 0b0000_0100 != 0,
+        alt:   b & 0b0000_1000 != 0,
+        ctrl:  b & 0b0001_0000 != 0,
+    }
+}
+
+/// Collects the body of a bracketed paste, just after reading `ESC [ 2 0 0 ~`.
+///
+/// Reads bytes until the `ESC [ 2 0 1 ~` end marker is seen, decoding the
+/// collected bytes as UTF-8. A lone `ESC` inside the pasted content does not
+/// terminate the paste; an unterminated paste at EOF flushes what was read.
+fn parse_bracketed_paste<I>(iter: &mut I) -> Key
+where
+    I: Iterator<Item = Result<u8, std::io::Error>>,
+{
+    const END: &[u8] = b"\x1b[201~";
+
+    let mut content: Vec<u8> = Vec::new();
+    let mut matched = 0;
+
+    loop {
+        match iter.next() {
+            Some(Ok(b)) if b == END[matched] => {
+                matched += 1;
+                if matched == END.len() {
+                    break;
+                }
+            }
+            Some(Ok(b)) => {
+                // Mismatch: the partially matched prefix was literal content.
+                if matched > 0 {
+                    content.extend_from_slice(&END[..matched]);
+                    matched = 0;
+                }
+                if b == END[0] {
+                    matched = 1;
+                } else {
+                    content.push(b);
+                }
+            }
+            // Unterminated paste at EOF: flush any partially matched end
+            // marker as literal content rather than dropping it.
+            _ => {
+                content.extend_from_slice(&END[..matched]);
+                break;
+            }
+        }
+    }
+
+    Key::Paste(String::from_utf8_lossy(&content).into_owned())
+}
+
 /// Parse `c` as either a single byte ASCII char or a variable size UTF-8 char.
 fn parse_utf8_char<I>(c: u8, iter: &mut I) -> Result<Result<char, Vec<u8>>, std::io::Error>
 where
@@ -381,3 +859,181 @@ impl Drop for Getch {
         termios::tcsetattr(&stdin, termios::SetArg::TCSADRAIN, &self.orig_term).unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed a full byte sequence through the parser as `getch` would.
+    fn parse(bytes: &[u8]) -> Key {
+        let (first, rest) = bytes.split_first().unwrap();
+        let mut iter = rest.iter().copied().map(Ok);
+        parse_key(*first, &mut iter).unwrap()
+    }
+
+    #[test]
+    fn modified_arrow_keys() {
+        // Ctrl+Right
+        assert_eq!(
+            parse(b"\x1b[1;5C"),
+            Key::Modified { key: Box::new(Key::Right), shift: false, ctrl: true, alt: false },
+        );
+        // Shift+Left
+        assert_eq!(
+            parse(b"\x1b[1;2D"),
+            Key::Modified { key: Box::new(Key::Left), shift: true, ctrl: false, alt: false },
+        );
+        // Ctrl+Alt+Up
+        assert_eq!(
+            parse(b"\x1b[1;7A"),
+            Key::Modified { key: Box::new(Key::Up), shift: false, ctrl: true, alt: true },
+        );
+    }
+
+    #[test]
+    fn modified_special_keys() {
+        // Shift+Delete
+        assert_eq!(
+            parse(b"\x1b[3;2~"),
+            Key::Modified { key: Box::new(Key::Delete), shift: true, ctrl: false, alt: false },
+        );
+        // Ctrl+Shift+Alt+End
+        assert_eq!(
+            parse(b"\x1b[4;8~"),
+            Key::Modified { key: Box::new(Key::End), shift: true, ctrl: true, alt: true },
+        );
+    }
+
+    #[test]
+    fn bracketed_paste() {
+        assert_eq!(
+            parse(b"\x1b[200~hello world\x1b[201~"),
+            Key::Paste("hello world".to_string()),
+        );
+    }
+
+    #[test]
+    fn bracketed_paste_with_embedded_esc_and_newlines() {
+        assert_eq!(
+            parse(b"\x1b[200~a\nb\x1bc\x1b[201~"),
+            Key::Paste("a\nb\x1bc".to_string()),
+        );
+    }
+
+    #[test]
+    fn unterminated_bracketed_paste_flushes() {
+        assert_eq!(parse(b"\x1b[200~partial"), Key::Paste("partial".to_string()));
+    }
+
+    #[test]
+    fn unterminated_bracketed_paste_flushes_partial_end_marker() {
+        // A truncated end marker at EOF must be preserved as literal content.
+        assert_eq!(
+            parse(b"\x1b[200~abc\x1b[20"),
+            Key::Paste("abc\x1b[20".to_string()),
+        );
+    }
+
+    #[test]
+    fn into_bytes_round_trips() {
+        let keys = [
+            Key::EOF,
+            Key::Backspace,
+            Key::Delete,
+            Key::Esc,
+            Key::Up,
+            Key::Down,
+            Key::Right,
+            Key::Left,
+            Key::End,
+            Key::Home,
+            Key::BackTab,
+            Key::Insert,
+            Key::PageUp,
+            Key::PageDown,
+            Key::F(1),
+            Key::F(4),
+            Key::F(5),
+            Key::F(10),
+            Key::F(12),
+            Key::Char('a'),
+            Key::Char('あ'),
+            Key::Alt('x'),
+            Key::Ctrl('a'),
+            Key::Ctrl('z'),
+            Key::Modified { key: Box::new(Key::Right), shift: false, ctrl: true, alt: false },
+            Key::Modified { key: Box::new(Key::Delete), shift: true, ctrl: false, alt: false },
+            Key::Modified { key: Box::new(Key::F(1)), shift: false, ctrl: true, alt: false },
+            Key::Modified { key: Box::new(Key::F(5)), shift: true, ctrl: true, alt: true },
+            Key::Modified { key: Box::new(Key::Up), shift: true, ctrl: true, alt: true },
+            Key::Mouse { button: 0, x: 10, y: 20, pressed: true, shift: false, ctrl: false, alt: false },
+            Key::Mouse { button: 2, x: 3, y: 4, pressed: false, shift: false, ctrl: true, alt: false },
+            Key::Paste("hello\nworld".to_string()),
+        ];
+        for key in keys {
+            let bytes = key.clone().into_bytes();
+            assert_eq!(parse(&bytes), key, "round-trip failed for {key:?}");
+        }
+    }
+
+    #[test]
+    fn into_bytes_spot_checks() {
+        assert_eq!(Key::Up.into_bytes(), b"\x1b[A");
+        assert_eq!(Key::F(1).into_bytes(), b"\x1bOP");
+        assert_eq!(Key::Ctrl('a').into_bytes(), b"\x01");
+        assert_eq!(Key::Alt('x').into_bytes(), b"\x1bx");
+        assert_eq!(Key::Other(vec![1, 2, 3]).into_bytes(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn sgr_mouse_press_and_release() {
+        // Left button press at column 10, row 20.
+        assert_eq!(
+            parse(b"\x1b[<0;10;20M"),
+            Key::Mouse { button: 0, x: 10, y: 20, pressed: true, shift: false, ctrl: false, alt: false },
+        );
+        // Left button release.
+        assert_eq!(
+            parse(b"\x1b[<0;10;20m"),
+            Key::Mouse { button: 0, x: 10, y: 20, pressed: false, shift: false, ctrl: false, alt: false },
+        );
+    }
+
+    #[test]
+    fn sgr_mouse_with_modifiers_and_wheel() {
+        // Ctrl-held right-button press (2 | 16 = 18).
+        assert_eq!(
+            parse(b"\x1b[<18;3;4M"),
+            Key::Mouse { button: 2, x: 3, y: 4, pressed: true, shift: false, ctrl: true, alt: false },
+        );
+        // Wheel up (64) keeps its button bits after modifiers are stripped.
+        assert_eq!(
+            parse(b"\x1b[<64;1;1M"),
+            Key::Mouse { button: 64, x: 1, y: 1, pressed: true, shift: false, ctrl: false, alt: false },
+        );
+    }
+
+    #[test]
+    fn malformed_mouse_falls_back_to_other() {
+        assert_eq!(parse(b"\x1b[<0;10M"), Key::Other(b"\x1b[<0;10M".to_vec()));
+    }
+
+    #[test]
+    fn keypad_cursor_keys() {
+        assert_eq!(parse(b"\x1bOA"), Key::KeypadUp);
+        assert_eq!(parse(b"\x1bOB"), Key::KeypadDown);
+        assert_eq!(parse(b"\x1bOC"), Key::KeypadRight);
+        assert_eq!(parse(b"\x1bOD"), Key::KeypadLeft);
+        assert_eq!(parse(b"\x1bOH"), Key::KeypadHome);
+        assert_eq!(parse(b"\x1bOF"), Key::KeypadEnd);
+        // F1-F4 on `ESC O` are still recognized.
+        assert_eq!(parse(b"\x1bOP"), Key::F(1));
+    }
+
+    #[test]
+    fn unmodified_sequences_are_unchanged() {
+        assert_eq!(parse(b"\x1b[C"), Key::Right);
+        assert_eq!(parse(b"\x1b[3~"), Key::Delete);
+        assert_eq!(parse(b"\x1b[15~"), Key::F(5));
+    }
+}